@@ -0,0 +1,83 @@
+//! Launch-on-login.
+//!
+//! Lets the user have GatewayZ start automatically on boot - a natural fit
+//! given the app already behaves as a background tray resident. On Windows
+//! the login-triggered start stays minimized to tray; on other platforms
+//! the registered command carries no hidden flag, so the window is shown. Backed by the `auto-launch` crate, which handles the
+//! platform-specific mechanism (a LaunchAgent on macOS, an autostart
+//! `.desktop` entry on Linux, a registry Run key on Windows).
+
+use auto_launch::{AutoLaunch, AutoLaunchBuilder};
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+const STORE_FILE: &str = "settings.json";
+const STORE_KEY: &str = "launch_at_login";
+const APP_NAME: &str = "GatewayZ";
+
+/// Flag appended to the registered launch command on Windows so a
+/// login-triggered start opens straight into the tray instead of flashing
+/// the main window before it's minimized.
+const HIDDEN_FLAG: &str = "--hidden";
+
+/// Whether the process was launched with the hidden-start flag (set on the
+/// autostart command so a login-triggered launch doesn't flash the main
+/// window before it's minimized to tray).
+pub fn launched_hidden() -> bool {
+    std::env::args().any(|arg| arg == HIDDEN_FLAG)
+}
+
+fn auto_launch() -> Result<AutoLaunch, String> {
+    let exe_path = std::env::current_exe()
+        .map_err(|e| e.to_string())?
+        .to_string_lossy()
+        .to_string();
+
+    let mut builder = AutoLaunchBuilder::new();
+    builder.set_app_name(APP_NAME).set_app_path(&exe_path);
+
+    #[cfg(target_os = "windows")]
+    builder.set_args(&[HIDDEN_FLAG]);
+
+    builder.build().map_err(|e| e.to_string())
+}
+
+/// Enable or disable launching GatewayZ at login, and persist the choice.
+#[tauri::command]
+pub fn set_launch_at_login(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let auto = auto_launch()?;
+
+    if enabled {
+        auto.enable().map_err(|e| e.to_string())?;
+    } else {
+        auto.disable().map_err(|e| e.to_string())?;
+    }
+
+    let store = app.store(STORE_FILE).map_err(|e| e.to_string())?;
+    store.set(STORE_KEY, serde_json::json!(enabled));
+    store.save().map_err(|e| e.to_string())
+}
+
+/// Whether GatewayZ is currently registered to launch at login.
+#[tauri::command]
+pub fn get_launch_at_login() -> Result<bool, String> {
+    auto_launch()?.is_enabled().map_err(|e| e.to_string())
+}
+
+/// Re-apply the persisted preference to the OS-level autostart entry.
+/// Called once from `setup`, so e.g. an app path change after an update
+/// re-registers autostart against the current executable. Returns the
+/// persisted enabled state for the tray menu to reflect.
+pub fn apply_persisted(app: &AppHandle) -> Result<bool, String> {
+    let store = app.store(STORE_FILE).map_err(|e| e.to_string())?;
+    let enabled = store
+        .get(STORE_KEY)
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    if enabled {
+        auto_launch()?.enable().map_err(|e| e.to_string())?;
+    }
+
+    Ok(enabled)
+}
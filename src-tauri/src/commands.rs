@@ -6,9 +6,11 @@
 use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Manager, WebviewWindow};
 use tauri_plugin_notification::NotificationExt;
-use tauri_plugin_store::StoreExt;
 use tauri_plugin_updater::UpdaterExt;
 
+use crate::proxy;
+use crate::secure_storage;
+
 /// Application version information
 #[derive(Debug, Clone, Serialize)]
 pub struct AppVersion {
@@ -93,44 +95,41 @@ pub async fn open_external_url(url: String) -> Result<(), String> {
     open::that(&url).map_err(|e| e.to_string())
 }
 
-/// Get the stored authentication token
+/// Get the stored authentication token from secure storage
 #[tauri::command]
 pub async fn get_auth_token(app: AppHandle) -> Result<Option<String>, String> {
-    let store = app.store("auth.json").map_err(|e| e.to_string())?;
-
-    let token = store
-        .get("auth_token")
-        .and_then(|v| v.as_str().map(String::from));
-
-    Ok(token)
+    secure_storage::load_token(&app)
 }
 
-/// Store the authentication token securely
+/// Store the authentication token in the OS keyring (or the encrypted
+/// fallback file if no keyring backend is available)
 #[tauri::command]
 pub async fn set_auth_token(app: AppHandle, token: String) -> Result<(), String> {
-    let store = app.store("auth.json").map_err(|e| e.to_string())?;
-
-    store.set("auth_token", serde_json::json!(token));
-    store.save().map_err(|e| e.to_string())?;
-
-    Ok(())
+    secure_storage::store_token(&app, &token)
 }
 
-/// Clear the stored authentication token
+/// Clear the stored authentication token from secure storage
 #[tauri::command]
 pub async fn clear_auth_token(app: AppHandle) -> Result<(), String> {
-    let store = app.store("auth.json").map_err(|e| e.to_string())?;
+    secure_storage::clear_token(&app)
+}
 
-    store.delete("auth_token");
-    store.save().map_err(|e| e.to_string())?;
+/// Build an updater, routed through the resolved proxy (explicit override,
+/// else environment), if one is configured.
+fn build_updater(app: &AppHandle) -> Result<tauri_plugin_updater::Updater, String> {
+    let mut builder = app.updater_builder();
 
-    Ok(())
+    if let Some(proxy_url) = proxy::resolve(app)? {
+        builder = builder.proxy(proxy_url);
+    }
+
+    builder.build().map_err(|e| e.to_string())
 }
 
 /// Check for application updates
 #[tauri::command]
 pub async fn check_for_updates(app: AppHandle) -> Result<UpdateInfo, String> {
-    let updater = app.updater().map_err(|e| e.to_string())?;
+    let updater = build_updater(&app)?;
 
     match updater.check().await {
         Ok(Some(update)) => Ok(UpdateInfo {
@@ -152,7 +151,7 @@ pub async fn check_for_updates(app: AppHandle) -> Result<UpdateInfo, String> {
 /// Install a pending update
 #[tauri::command]
 pub async fn install_update(app: AppHandle) -> Result<(), String> {
-    let updater = app.updater().map_err(|e| e.to_string())?;
+    let updater = build_updater(&app)?;
 
     match updater.check().await {
         Ok(Some(update)) => {
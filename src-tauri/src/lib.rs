@@ -4,13 +4,26 @@
 //! including system tray management, keyboard shortcuts, and IPC commands.
 
 use tauri::{
-    menu::{Menu, MenuItem, PredefinedMenuItem},
+    menu::{CheckMenuItem, Menu, MenuItem, PredefinedMenuItem},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
     AppHandle, Emitter, Manager,
 };
 
+mod autostart;
 mod commands;
+mod overlay;
+mod proxy;
+mod secure_storage;
+mod shortcuts;
+mod window_state;
+mod windows;
+pub use autostart::{get_launch_at_login, set_launch_at_login};
 pub use commands::*;
+pub use overlay::set_overlay_mode;
+pub use proxy::set_proxy;
+pub use shortcuts::{clear_global_shortcut, list_global_shortcuts, set_global_shortcut};
+pub use window_state::reset_window_state;
+pub use windows::{list_windows, open_chat_window};
 
 /// Simple timestamp for logging (avoids adding chrono dependency)
 #[cfg(all(target_os = "windows", not(debug_assertions)))]
@@ -72,21 +85,48 @@ pub fn run() {
         .plugin(
             tauri_plugin_global_shortcut::Builder::new()
                 .with_handler(move |app, shortcut, event| {
-                    if event.state() == tauri_plugin_global_shortcut::ShortcutState::Pressed {
-                        handle_global_shortcut(app, shortcut);
-                    }
+                    shortcuts::dispatch(app, shortcut, event.state());
                 })
                 .build(),
         );
 
     builder
         .setup(|app| {
+            // Move any pre-existing plaintext auth token into secure storage
+            if let Err(e) = secure_storage::migrate_plaintext_token(app.handle()) {
+                log::warn!("Auth token migration to secure storage failed: {}", e);
+            }
+
+            // Restore the main window's saved geometry before it's shown
+            if let Some(window) = app.get_webview_window("main") {
+                window_state::restore(app.handle(), &window);
+
+                // Launched via the autostart entry's --hidden flag: stay
+                // minimized to tray instead of showing the window like a
+                // normal launch would.
+                if autostart::launched_hidden() {
+                    let _ = window.hide();
+                }
+            }
+
+            // Re-apply the persisted launch-at-login preference
+            let launch_at_login = autostart::apply_persisted(app.handle()).unwrap_or_else(|e| {
+                log::warn!("Failed to apply launch-at-login preference: {}", e);
+                false
+            });
+
+            // Re-apply the persisted overlay-mode preference
+            let overlay_mode = overlay::apply_persisted(app.handle()).unwrap_or_else(|e| {
+                log::warn!("Failed to apply overlay-mode preference: {}", e);
+                false
+            });
+
             // Set up the system tray
-            setup_tray(app.handle())?;
+            setup_tray(app.handle(), launch_at_login, overlay_mode)?;
 
             // Register global shortcuts
             #[cfg(desktop)]
-            register_shortcuts(app.handle())?;
+            shortcuts::register_all(app.handle())?;
 
             // Handle deep links
             setup_deep_link_handler(app.handle());
@@ -108,19 +148,40 @@ pub fn run() {
             commands::set_window_state,
             commands::toggle_always_on_top,
             commands::minimize_to_tray,
+            windows::open_chat_window,
+            windows::list_windows,
+            window_state::reset_window_state,
+            shortcuts::set_global_shortcut,
+            shortcuts::clear_global_shortcut,
+            shortcuts::list_global_shortcuts,
+            autostart::set_launch_at_login,
+            autostart::get_launch_at_login,
+            proxy::set_proxy,
+            overlay::set_overlay_mode,
         ])
-        .on_window_event(|window, event| {
-            // Handle window close event - minimize to tray instead of closing.
-            // This is intentional UX for desktop apps with system tray integration:
-            // - Users can fully quit via the tray menu "Quit" option
-            // - The tray icon indicates the app is still running
-            // - Alt+F4/Cmd+Q will also trigger this (use tray menu to fully quit)
-            // Future improvement: Add a user preference to toggle this behavior
-            if let tauri::WindowEvent::CloseRequested { api, .. } = event {
-                // Hide window instead of closing - app stays in tray
-                let _ = window.hide();
-                api.prevent_close();
+        .on_window_event(|window, event| match event {
+            tauri::WindowEvent::CloseRequested { api, .. } => {
+                let _ = window_state::save_now(window);
+
+                // Only the single persistent "main" window minimizes to tray
+                // instead of closing:
+                // - Users can fully quit via the tray menu "Quit" option
+                // - The tray icon indicates the app is still running
+                // - Alt+F4/Cmd+Q will also trigger this (use tray menu to fully quit)
+                // Future improvement: Add a user preference to toggle this behavior
+                //
+                // Secondary chat-N windows are ephemeral and actually close,
+                // so they don't pile up as hidden webviews for the rest of
+                // the app session with no way to bring them back.
+                if window.label() == "main" {
+                    let _ = window.hide();
+                    api.prevent_close();
+                }
+            }
+            tauri::WindowEvent::Moved(_) | tauri::WindowEvent::Resized(_) => {
+                window_state::save_debounced(window.clone());
             }
+            _ => {}
         })
         .run(tauri::generate_context!())
         .unwrap_or_else(|e| {
@@ -141,7 +202,11 @@ pub fn run() {
 }
 
 /// Set up the system tray icon and menu
-fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+fn setup_tray(
+    app: &AppHandle,
+    launch_at_login: bool,
+    overlay_mode: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
     let show = MenuItem::with_id(app, "show", "Show GatewayZ", true, None::<&str>)?;
     let new_chat = MenuItem::with_id(app, "new_chat", "New Chat", true, Some("CmdOrCtrl+N"))?;
     let separator = PredefinedMenuItem::separator(app)?;
@@ -153,6 +218,22 @@ fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
         None::<&str>,
     )?;
     let settings = MenuItem::with_id(app, "settings", "Settings...", true, Some("CmdOrCtrl+,"))?;
+    let launch_at_login_item = CheckMenuItem::with_id(
+        app,
+        "launch_at_login",
+        "Launch at Login",
+        true,
+        launch_at_login,
+        None::<&str>,
+    )?;
+    let overlay_mode_item = CheckMenuItem::with_id(
+        app,
+        "overlay_mode",
+        "Overlay Mode",
+        true,
+        overlay_mode,
+        None::<&str>,
+    )?;
     let separator2 = PredefinedMenuItem::separator(app)?;
     let quit = MenuItem::with_id(app, "quit", "Quit GatewayZ", true, Some("CmdOrCtrl+Q"))?;
 
@@ -164,6 +245,8 @@ fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
             &separator,
             &check_updates,
             &settings,
+            &launch_at_login_item,
+            &overlay_mode_item,
             &separator2,
             &quit,
         ],
@@ -179,30 +262,48 @@ fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
         .icon(icon)
         .menu(&menu)
         .show_menu_on_left_click(false)
-        .on_menu_event(|app, event| match event.id.as_ref() {
+        .on_menu_event(move |app, event| match event.id.as_ref() {
             "show" => {
-                if let Some(window) = app.get_webview_window("main") {
+                if let Some(window) = windows::focused_or_main(app) {
                     let _ = window.show();
                     let _ = window.set_focus();
                 }
             }
-            "new_chat" => {
-                if let Some(window) = app.get_webview_window("main") {
-                    let _ = window.show();
-                    let _ = window.set_focus();
-                    let _ = window.emit("new-chat", ());
+            "new_chat" => match windows::open_chat_window(app.clone(), None) {
+                Ok(label) => {
+                    if let Some(window) = app.get_webview_window(&label) {
+                        let _ = window.show();
+                        let _ = window.set_focus();
+                    }
                 }
-            }
+                Err(e) => log::error!("Failed to open new chat window: {}", e),
+            },
             "check_updates" => {
-                if let Some(window) = app.get_webview_window("main") {
-                    let _ = window.emit("check-updates", ());
-                }
+                let _ = windows::emit_to_all(app, "check-updates", (), |_| true);
             }
             "settings" => {
-                if let Some(window) = app.get_webview_window("main") {
+                if let Some(window) = windows::focused_or_main(app) {
                     let _ = window.show();
                     let _ = window.set_focus();
-                    let _ = window.emit("navigate", "/settings");
+                    let label = window.label().to_string();
+                    let _ =
+                        windows::emit_to_all(app, "navigate", "/settings", move |l| l == label);
+                }
+            }
+            "launch_at_login" => {
+                let enabled = launch_at_login_item.is_checked().unwrap_or(false);
+                if let Err(e) = autostart::set_launch_at_login(app.clone(), enabled) {
+                    log::error!("Failed to set launch at login: {}", e);
+                    // Revert the checkbox since the underlying change didn't take
+                    let _ = launch_at_login_item.set_checked(!enabled);
+                }
+            }
+            "overlay_mode" => {
+                let enabled = overlay_mode_item.is_checked().unwrap_or(false);
+                if let Err(e) = overlay::set_overlay_mode(app.clone(), enabled) {
+                    log::error!("Failed to set overlay mode: {}", e);
+                    // Revert the checkbox since the underlying change didn't take
+                    let _ = overlay_mode_item.set_checked(!enabled);
                 }
             }
             "quit" => {
@@ -229,60 +330,6 @@ fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-/// Register global keyboard shortcuts
-#[cfg(desktop)]
-fn register_shortcuts(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
-    use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Modifiers, Shortcut};
-
-    // Register platform-specific shortcuts to show/focus GatewayZ:
-    // - macOS: Cmd+G (Super+G)
-    // - Windows: Ctrl+G
-    // - Linux: Super+G
-    #[cfg(target_os = "windows")]
-    let shortcut = Shortcut::new(Some(Modifiers::CONTROL), Code::KeyG);
-
-    #[cfg(not(target_os = "windows"))]
-    let shortcut = Shortcut::new(Some(Modifiers::SUPER), Code::KeyG);
-
-    app.global_shortcut().register(shortcut)?;
-
-    log::info!("Global shortcuts registered");
-    Ok(())
-}
-
-/// Handle global shortcut events
-#[cfg(desktop)]
-fn handle_global_shortcut(app: &AppHandle, shortcut: &tauri_plugin_global_shortcut::Shortcut) {
-    use tauri_plugin_global_shortcut::Code;
-
-    if shortcut.key == Code::KeyG {
-        // Toggle the main window: show if hidden/minimized, hide if visible and focused
-        if let Some(window) = app.get_webview_window("main") {
-            // Check window state
-            let is_visible = window.is_visible().unwrap_or(false);
-            let is_focused = window.is_focused().unwrap_or(false);
-            let is_minimized = window.is_minimized().unwrap_or(false);
-
-            if is_minimized {
-                // Window is minimized - unminimize, show, and focus it
-                let _ = window.unminimize();
-                let _ = window.show();
-                let _ = window.set_focus();
-            } else if is_visible && is_focused {
-                // Window is visible and focused - hide it
-                let _ = window.hide();
-            } else if is_visible {
-                // Window is visible but not focused - bring it to focus
-                let _ = window.set_focus();
-            } else {
-                // Window is hidden - show and focus it
-                let _ = window.show();
-                let _ = window.set_focus();
-            }
-        }
-    }
-}
-
 /// Set up deep link handler for gatewayz:// protocol
 fn setup_deep_link_handler(app: &AppHandle) {
     use tauri::Listener;
@@ -328,46 +375,55 @@ fn handle_deep_link(app: &AppHandle, url: &url::Url) {
         }
     }
 
-    // Get the main window
-    if let Some(window) = app.get_webview_window("main") {
-        let _ = window.show();
-        let _ = window.set_focus();
-
-        // Route based on the URL path
-        match url.path() {
-            "/chat" => {
-                // Open a new chat or specific chat
-                if let Some(chat_id) = url.query_pairs().find(|(k, _)| k == "id").map(|(_, v)| v) {
-                    let _ = window.emit("navigate", format!("/chat?id={}", chat_id));
-                } else {
-                    let _ = window.emit("new-chat", ());
+    // Route based on the URL path
+    match url.path() {
+        "/chat" => {
+            // Open a new window for the chat instead of reusing main, so a
+            // deep link doesn't clobber whatever the user already has open
+            let chat_id = url.query_pairs().find(|(k, _)| k == "id").map(|(_, v)| v.to_string());
+            match windows::open_chat_window(app.clone(), chat_id) {
+                Ok(label) => {
+                    if let Some(window) = app.get_webview_window(&label) {
+                        let _ = window.show();
+                        let _ = window.set_focus();
+                    }
                 }
+                Err(e) => log::error!("Failed to open chat window for deep link: {}", e),
             }
-            "/auth/callback" => {
-                // Handle OAuth callback
-                log::info!("Auth callback received, emitting auth-callback event");
-                #[cfg(all(target_os = "windows", not(debug_assertions)))]
+        }
+        "/auth/callback" => {
+            // Handle OAuth callback - target the focused window, else main
+            log::info!("Auth callback received, emitting auth-callback event");
+            #[cfg(all(target_os = "windows", not(debug_assertions)))]
+            {
+                use std::io::Write;
+                if let Ok(mut file) = std::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(std::env::temp_dir().join("gatewayz-desktop.log"))
                 {
-                    use std::io::Write;
-                    if let Ok(mut file) = std::fs::OpenOptions::new()
-                        .create(true)
-                        .append(true)
-                        .open(std::env::temp_dir().join("gatewayz-desktop.log"))
-                    {
-                        let _ =
-                            writeln!(file, "[{}] Auth callback - emitting event", chrono_lite());
-                    }
+                    let _ = writeln!(file, "[{}] Auth callback - emitting event", chrono_lite());
                 }
-                let query = url.query().unwrap_or("");
-                let _ = window.emit("auth-callback", query);
             }
-            _ => {
-                // Navigate to the path directly via event
-                let path = url.path();
-                let _ = window.emit("navigate", path);
+            if let Some(window) = windows::focused_or_main(app) {
+                let _ = window.show();
+                let _ = window.set_focus();
+                let query = url.query().unwrap_or("").to_string();
+                let label = window.label().to_string();
+                let _ = windows::emit_to_all(app, "auth-callback", query, move |l| l == label);
+            }
+        }
+        _ => {
+            // Navigate the focused window (else main) to the path directly
+            if let Some(window) = windows::focused_or_main(app) {
+                let _ = window.show();
+                let _ = window.set_focus();
+                let path = url.path().to_string();
+                let label = window.label().to_string();
+                let _ = windows::emit_to_all(app, "navigate", path, move |l| l == label);
+            } else {
+                log::error!("No window available to handle deep link navigation");
             }
         }
-    } else {
-        log::error!("Failed to get main window for deep link handling");
     }
 }
@@ -0,0 +1,56 @@
+//! Overlay assistant mode.
+//!
+//! Combines always-on-top with "visible on all workspaces / virtual
+//! desktops" so GatewayZ can act as a persistent quick-access assistant
+//! that stays reachable regardless of which desktop the user switches to.
+//! This extends the plain `toggle_always_on_top` command, which only
+//! covers the always-on-top half.
+
+use tauri::{AppHandle, Manager, WebviewWindow};
+use tauri_plugin_store::StoreExt;
+
+const STORE_FILE: &str = "settings.json";
+const STORE_KEY: &str = "overlay_mode";
+
+fn apply(window: &WebviewWindow, enabled: bool) -> Result<(), String> {
+    window
+        .set_always_on_top(enabled)
+        .map_err(|e| e.to_string())?;
+    window
+        .set_visible_on_all_workspaces(enabled)
+        .map_err(|e| e.to_string())
+}
+
+/// Enable or disable overlay mode on the main window, and persist the
+/// preference so it's restored on next launch.
+#[tauri::command]
+pub fn set_overlay_mode(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let window = app
+        .get_webview_window("main")
+        .ok_or_else(|| "main window not found".to_string())?;
+
+    apply(&window, enabled)?;
+
+    let store = app.store(STORE_FILE).map_err(|e| e.to_string())?;
+    store.set(STORE_KEY, serde_json::json!(enabled));
+    store.save().map_err(|e| e.to_string())
+}
+
+/// Re-apply the persisted overlay preference to the main window. Called
+/// once from `setup`. Returns the persisted enabled state for the tray
+/// menu to reflect.
+pub fn apply_persisted(app: &AppHandle) -> Result<bool, String> {
+    let store = app.store(STORE_FILE).map_err(|e| e.to_string())?;
+    let enabled = store
+        .get(STORE_KEY)
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    if enabled {
+        if let Some(window) = app.get_webview_window("main") {
+            apply(&window, true)?;
+        }
+    }
+
+    Ok(enabled)
+}
@@ -0,0 +1,68 @@
+//! Proxy-aware networking.
+//!
+//! Corporate/self-hosted users behind a proxy need update checks (and any
+//! other outbound request) to go through it. An explicit `set_proxy`
+//! override takes precedence; otherwise the standard `HTTP_PROXY` /
+//! `HTTPS_PROXY` / `ALL_PROXY` environment variables are honoured,
+//! including `socks5://` values.
+
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+use url::Url;
+
+const STORE_FILE: &str = "settings.json";
+const STORE_KEY: &str = "proxy_url";
+
+fn env_proxy() -> Option<String> {
+    for var in [
+        "HTTPS_PROXY",
+        "https_proxy",
+        "ALL_PROXY",
+        "all_proxy",
+        "HTTP_PROXY",
+        "http_proxy",
+    ] {
+        if let Ok(value) = std::env::var(var) {
+            if !value.is_empty() {
+                return Some(value);
+            }
+        }
+    }
+    None
+}
+
+/// Persist an explicit proxy override, taking precedence over environment
+/// detection. Pass `None` to clear it and fall back to the environment.
+#[tauri::command]
+pub fn set_proxy(app: AppHandle, url: Option<String>) -> Result<(), String> {
+    if let Some(url) = &url {
+        Url::parse(url).map_err(|e| format!("invalid proxy URL '{}': {}", url, e))?;
+    }
+
+    let store = app.store(STORE_FILE).map_err(|e| e.to_string())?;
+    match &url {
+        Some(url) => store.set(STORE_KEY, serde_json::json!(url)),
+        None => store.delete(STORE_KEY),
+    };
+    store.save().map_err(|e| e.to_string())
+}
+
+/// Resolve the proxy to use: the persisted explicit override if set,
+/// otherwise `HTTP_PROXY`/`HTTPS_PROXY`/`ALL_PROXY` from the environment,
+/// otherwise none. Returns an error (rather than letting a bad URL surface
+/// later as a silent connection timeout) if the resolved value isn't a
+/// valid URL.
+pub fn resolve(app: &AppHandle) -> Result<Option<Url>, String> {
+    let explicit = app
+        .store(STORE_FILE)
+        .ok()
+        .and_then(|store| store.get(STORE_KEY))
+        .and_then(|v| v.as_str().map(String::from));
+
+    match explicit.or_else(env_proxy) {
+        Some(raw) => Url::parse(&raw)
+            .map(Some)
+            .map_err(|e| format!("invalid proxy URL '{}': {}", raw, e)),
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,152 @@
+//! Secure storage for the auth token.
+//!
+//! Prefers the OS keyring (Secret Service on Linux, Keychain on macOS,
+//! Credential Manager on Windows) via the `keyring` crate, so the bearer
+//! token never touches disk in plaintext. When no keyring backend is
+//! available (headless Linux, some sandboxes) falls back to a file
+//! encrypted with a key derived via Argon2 from a machine-bound identifier.
+
+use std::path::PathBuf;
+
+use aes_gcm::aead::{Aead, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Nonce};
+use argon2::Argon2;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_store::StoreExt;
+
+const SERVICE: &str = "com.gatewayz.desktop";
+const ACCOUNT: &str = "auth_token";
+const FALLBACK_FILE: &str = "auth_token.enc";
+const FALLBACK_SALT: &[u8] = b"gatewayz-desktop-auth-token-v1";
+
+#[derive(Serialize, Deserialize)]
+struct EncryptedBlob {
+    nonce: Vec<u8>,
+    ciphertext: Vec<u8>,
+}
+
+fn entry() -> keyring::Result<keyring::Entry> {
+    keyring::Entry::new(SERVICE, ACCOUNT)
+}
+
+/// Derive a 256-bit key from a machine-bound identifier, so the encrypted
+/// fallback file can't simply be copied to another machine and decrypted.
+/// If the machine identifier can't be read, a fixed string is used instead,
+/// and the resulting key is then the same on every such machine.
+fn machine_key() -> Result<[u8; 32], String> {
+    let machine_id = machine_uid::get().unwrap_or_else(|_| "gatewayz-fallback".to_string());
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(machine_id.as_bytes(), FALLBACK_SALT, &mut key)
+        .map_err(|e| e.to_string())?;
+    Ok(key)
+}
+
+fn fallback_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir.join(FALLBACK_FILE))
+}
+
+fn fallback_set(app: &AppHandle, token: &str) -> Result<(), String> {
+    let cipher = Aes256Gcm::new_from_slice(&machine_key()?).map_err(|e| e.to_string())?;
+
+    let mut nonce_bytes = [0u8; 12];
+    OsRng.fill_bytes(&mut nonce_bytes);
+    let nonce = Nonce::from_slice(&nonce_bytes);
+
+    let ciphertext = cipher
+        .encrypt(nonce, token.as_bytes())
+        .map_err(|e| e.to_string())?;
+
+    let blob = EncryptedBlob {
+        nonce: nonce_bytes.to_vec(),
+        ciphertext,
+    };
+
+    let data = serde_json::to_vec(&blob).map_err(|e| e.to_string())?;
+    std::fs::write(fallback_path(app)?, data).map_err(|e| e.to_string())
+}
+
+fn fallback_get(app: &AppHandle) -> Result<Option<String>, String> {
+    let path = fallback_path(app)?;
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let data = std::fs::read(path).map_err(|e| e.to_string())?;
+    let blob: EncryptedBlob = serde_json::from_slice(&data).map_err(|e| e.to_string())?;
+
+    let cipher = Aes256Gcm::new_from_slice(&machine_key()?).map_err(|e| e.to_string())?;
+    let nonce = Nonce::from_slice(&blob.nonce);
+    let plaintext = cipher
+        .decrypt(nonce, blob.ciphertext.as_ref())
+        .map_err(|e| e.to_string())?;
+
+    String::from_utf8(plaintext)
+        .map(Some)
+        .map_err(|e| e.to_string())
+}
+
+fn fallback_clear(app: &AppHandle) -> Result<(), String> {
+    let path = fallback_path(app)?;
+    if path.exists() {
+        std::fs::remove_file(path).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Store `token` in the platform keyring, falling back to the
+/// Argon2/AES-GCM-encrypted file when no keyring backend is available.
+pub fn store_token(app: &AppHandle, token: &str) -> Result<(), String> {
+    match entry().and_then(|e| e.set_password(token)) {
+        Ok(()) => Ok(()),
+        Err(_) => fallback_set(app, token),
+    }
+}
+
+/// Read the token from the platform keyring, falling back to the encrypted
+/// file if the keyring is unavailable, erroring, or has no entry.
+///
+/// A `NoEntry` keyring result doesn't necessarily mean no token exists: if
+/// an earlier `store_token` call hit a transient keyring error (locked
+/// keyring, D-Bus timeout, etc.) it would have written the token to the
+/// fallback file instead, and the keyring would correctly, but misleadingly,
+/// report `NoEntry` forever after. So `NoEntry` must check the fallback
+/// file too, not short-circuit to `None`.
+pub fn load_token(app: &AppHandle) -> Result<Option<String>, String> {
+    match entry().and_then(|e| e.get_password()) {
+        Ok(token) => Ok(Some(token)),
+        Err(_) => fallback_get(app),
+    }
+}
+
+/// Remove the token from both the keyring and the encrypted fallback file.
+pub fn clear_token(app: &AppHandle) -> Result<(), String> {
+    if let Ok(entry) = entry() {
+        let _ = entry.delete_credential();
+    }
+    fallback_clear(app)
+}
+
+/// One-time migration: if `auth.json` still holds a plaintext `auth_token`
+/// from before secure storage existed, move it into the keyring/fallback
+/// and strip it from the store. Safe to call on every launch; it's a no-op
+/// once the plaintext entry is gone.
+pub fn migrate_plaintext_token(app: &AppHandle) -> Result<(), String> {
+    let store = app.store("auth.json").map_err(|e| e.to_string())?;
+
+    if let Some(token) = store
+        .get("auth_token")
+        .and_then(|v| v.as_str().map(String::from))
+    {
+        store_token(app, &token)?;
+        store.delete("auth_token");
+        store.save().map_err(|e| e.to_string())?;
+        log::info!("Migrated plaintext auth token into secure storage");
+    }
+
+    Ok(())
+}
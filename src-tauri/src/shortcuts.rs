@@ -0,0 +1,211 @@
+//! User-configurable, persisted global shortcuts.
+//!
+//! Each shortcut is keyed by an abstract action name (e.g.
+//! `"toggle-window"`) rather than by the accelerator itself, so a user can
+//! rebind the accelerator without the app losing track of what it's bound
+//! to. The action -> accelerator map is persisted in the store and
+//! reapplied on startup; the live `Shortcut -> action` bindings are kept in
+//! memory so the central handler can look up which action a pressed
+//! shortcut maps to.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{Mutex, OnceLock};
+
+use tauri::{AppHandle, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+use tauri_plugin_store::StoreExt;
+
+const STORE_FILE: &str = "shortcuts.json";
+
+/// Toggle the main window's visibility.
+pub const ACTION_TOGGLE_WINDOW: &str = "toggle-window";
+/// Open a new chat window.
+pub const ACTION_NEW_CHAT: &str = "new-chat";
+
+#[derive(Default)]
+struct Bindings {
+    /// action -> accelerator string, mirrors what's persisted in the store
+    accelerators: HashMap<String, String>,
+    /// live shortcut -> action, used by the dispatcher
+    live: HashMap<Shortcut, String>,
+}
+
+fn bindings() -> &'static Mutex<Bindings> {
+    static BINDINGS: OnceLock<Mutex<Bindings>> = OnceLock::new();
+    BINDINGS.get_or_init(|| Mutex::new(Bindings::default()))
+}
+
+/// Platform-specific default so a fresh install still has a way to summon
+/// the window: macOS/Linux use Super+G, Windows uses Ctrl+G (Super is
+/// reserved by the OS there).
+fn default_accelerator(action: &str) -> Option<&'static str> {
+    match action {
+        #[cfg(target_os = "windows")]
+        a if a == ACTION_TOGGLE_WINDOW => Some("Ctrl+G"),
+        #[cfg(not(target_os = "windows"))]
+        a if a == ACTION_TOGGLE_WINDOW => Some("Super+G"),
+        _ => None,
+    }
+}
+
+fn persisted_map(app: &AppHandle) -> HashMap<String, String> {
+    app.store(STORE_FILE)
+        .ok()
+        .and_then(|store| store.get("bindings"))
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+fn persist_map(app: &AppHandle, map: &HashMap<String, String>) -> Result<(), String> {
+    let store = app.store(STORE_FILE).map_err(|e| e.to_string())?;
+    store.set(
+        "bindings",
+        serde_json::to_value(map).map_err(|e| e.to_string())?,
+    );
+    store.save().map_err(|e| e.to_string())
+}
+
+fn bind(app: &AppHandle, action: &str, accelerator: &str) -> Result<(), String> {
+    let shortcut = Shortcut::from_str(accelerator)
+        .map_err(|e| format!("invalid accelerator '{}': {}", accelerator, e))?;
+
+    {
+        let guard = bindings().lock().unwrap();
+        if let Some(existing_action) = guard.live.get(&shortcut) {
+            if existing_action != action {
+                return Err(format!(
+                    "'{}' is already bound to '{}'",
+                    accelerator, existing_action
+                ));
+            }
+        }
+    }
+
+    app.global_shortcut()
+        .register(shortcut)
+        .map_err(|e| e.to_string())?;
+
+    let mut guard = bindings().lock().unwrap();
+    guard.live.insert(shortcut, action.to_string());
+    guard
+        .accelerators
+        .insert(action.to_string(), accelerator.to_string());
+
+    Ok(())
+}
+
+fn unbind(app: &AppHandle, action: &str) {
+    let mut guard = bindings().lock().unwrap();
+    if let Some(accelerator) = guard.accelerators.remove(action) {
+        if let Ok(shortcut) = Shortcut::from_str(&accelerator) {
+            guard.live.remove(&shortcut);
+            let _ = app.global_shortcut().unregister(shortcut);
+        }
+    }
+}
+
+/// Register the persisted (or, for known actions, default) shortcut for
+/// every binding. Called once from `setup`.
+pub fn register_all(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+    let mut map = persisted_map(app);
+
+    for action in [ACTION_TOGGLE_WINDOW, ACTION_NEW_CHAT] {
+        if !map.contains_key(action) {
+            if let Some(default) = default_accelerator(action) {
+                map.insert(action.to_string(), default.to_string());
+            }
+        }
+    }
+
+    for (action, accelerator) in map.clone() {
+        if let Err(e) = bind(app, &action, &accelerator) {
+            log::warn!("Failed to register shortcut for '{}': {}", action, e);
+        }
+    }
+
+    persist_map(app, &map)?;
+    log::info!("Global shortcuts registered");
+    Ok(())
+}
+
+/// Rebind `action` to `accelerator`, unregistering whatever it was
+/// previously bound to. Rejects accelerators that fail to parse or that
+/// collide with a different action's binding, leaving the old binding
+/// (if any) intact.
+#[tauri::command]
+pub fn set_global_shortcut(
+    app: AppHandle,
+    action: String,
+    accelerator: String,
+) -> Result<(), String> {
+    let previous = bindings().lock().unwrap().accelerators.get(&action).cloned();
+
+    unbind(&app, &action);
+    if let Err(e) = bind(&app, &action, &accelerator) {
+        if let Some(previous) = previous {
+            let _ = bind(&app, &action, &previous);
+        }
+        return Err(e);
+    }
+
+    let map = bindings().lock().unwrap().accelerators.clone();
+    persist_map(&app, &map)
+}
+
+/// Unregister `action`'s shortcut, if any, leaving it unbound.
+#[tauri::command]
+pub fn clear_global_shortcut(app: AppHandle, action: String) -> Result<(), String> {
+    unbind(&app, &action);
+    let map = bindings().lock().unwrap().accelerators.clone();
+    persist_map(&app, &map)
+}
+
+/// The current action -> accelerator bindings.
+#[tauri::command]
+pub fn list_global_shortcuts() -> HashMap<String, String> {
+    bindings().lock().unwrap().accelerators.clone()
+}
+
+/// Look up which action a pressed shortcut maps to and perform it directly
+/// (toggle the main window, or open a new chat window); no event is emitted
+/// to the frontend. Called from the global shortcut plugin's handler.
+pub fn dispatch(app: &AppHandle, shortcut: &Shortcut, state: ShortcutState) {
+    if state != ShortcutState::Pressed {
+        return;
+    }
+
+    let action = bindings().lock().unwrap().live.get(shortcut).cloned();
+
+    match action.as_deref() {
+        Some(a) if a == ACTION_TOGGLE_WINDOW => toggle_main_window(app),
+        Some(a) if a == ACTION_NEW_CHAT => {
+            if let Err(e) = crate::windows::open_chat_window(app.clone(), None) {
+                log::error!("Failed to open chat window from shortcut: {}", e);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Toggle the main window: show if hidden/minimized, hide if visible and focused
+fn toggle_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let is_visible = window.is_visible().unwrap_or(false);
+        let is_focused = window.is_focused().unwrap_or(false);
+        let is_minimized = window.is_minimized().unwrap_or(false);
+
+        if is_minimized {
+            let _ = window.unminimize();
+            let _ = window.show();
+            let _ = window.set_focus();
+        } else if is_visible && is_focused {
+            let _ = window.hide();
+        } else if is_visible {
+            let _ = window.set_focus();
+        } else {
+            let _ = window.show();
+            let _ = window.set_focus();
+        }
+    }
+}
@@ -0,0 +1,151 @@
+//! Automatic window-state persistence.
+//!
+//! Window geometry (position/size/maximized/fullscreen) is saved per window
+//! label on move/resize (debounced, since those events fire continuously
+//! while dragging) and immediately on close, then restored the next time a
+//! window with that label is created - including a multi-monitor sanity
+//! check that clamps the restored position back onto a visible monitor when
+//! the saved coordinates fall off-screen (a common failure after a display
+//! is unplugged).
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager, Window};
+use tauri_plugin_store::StoreExt;
+
+use crate::commands::WindowState;
+
+const STORE_FILE: &str = "window-state.json";
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+fn generations() -> &'static Mutex<HashMap<String, u64>> {
+    static GENERATIONS: OnceLock<Mutex<HashMap<String, u64>>> = OnceLock::new();
+    GENERATIONS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn read_state(app: &AppHandle, label: &str) -> Option<WindowState> {
+    let store = app.store(STORE_FILE).ok()?;
+    serde_json::from_value(store.get(label)?).ok()
+}
+
+fn write_state(app: &AppHandle, label: &str, state: &WindowState) -> Result<(), String> {
+    let store = app.store(STORE_FILE).map_err(|e| e.to_string())?;
+    store.set(label, serde_json::to_value(state).map_err(|e| e.to_string())?);
+    store.save().map_err(|e| e.to_string())
+}
+
+/// Capture and persist `window`'s current geometry immediately.
+pub fn save_now(window: &Window) -> Result<(), String> {
+    let position = window.outer_position().map_err(|e| e.to_string())?;
+    let size = window.outer_size().map_err(|e| e.to_string())?;
+    let maximized = window.is_maximized().map_err(|e| e.to_string())?;
+    let fullscreen = window.is_fullscreen().map_err(|e| e.to_string())?;
+
+    let state = WindowState {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+        maximized,
+        fullscreen,
+    };
+
+    write_state(&window.app_handle().clone(), window.label(), &state)
+}
+
+/// Schedule a save of `window`'s geometry after a short debounce, so a
+/// drag-resize doesn't write to the store dozens of times a second. Only
+/// the last scheduled save for a given label actually runs.
+pub fn save_debounced(window: Window) {
+    let label = window.label().to_string();
+    let generation = {
+        let mut generations = generations().lock().unwrap();
+        let entry = generations.entry(label.clone()).or_insert(0);
+        *entry += 1;
+        *entry
+    };
+
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(DEBOUNCE).await;
+
+        let is_current = generations()
+            .lock()
+            .unwrap()
+            .get(&label)
+            .copied()
+            .map(|g| g == generation)
+            .unwrap_or(false);
+
+        if is_current {
+            let _ = save_now(&window);
+        }
+    });
+}
+
+/// Clamp a restored position back onto a visible monitor, so a window
+/// doesn't reopen off-screen after the display it was on is unplugged.
+fn clamp_to_visible_monitor(window: &Window, mut state: WindowState) -> WindowState {
+    let Ok(monitors) = window.available_monitors() else {
+        return state;
+    };
+
+    let on_screen = monitors.iter().any(|m| {
+        let pos = m.position();
+        let size = m.size();
+        state.x >= pos.x
+            && state.y >= pos.y
+            && (state.x as i64) < pos.x as i64 + size.width as i64
+            && (state.y as i64) < pos.y as i64 + size.height as i64
+    });
+
+    if !on_screen {
+        if let Some(monitor) = window
+            .primary_monitor()
+            .ok()
+            .flatten()
+            .or_else(|| monitors.into_iter().next())
+        {
+            let pos = monitor.position();
+            state.x = pos.x + 50;
+            state.y = pos.y + 50;
+        }
+    }
+
+    state
+}
+
+/// Restore `window`'s saved geometry, if any, clamping it back onto a
+/// visible monitor first. Call before the window is shown.
+pub fn restore(app: &AppHandle, window: &Window) {
+    let Some(state) = read_state(app, window.label()) else {
+        return;
+    };
+
+    let state = clamp_to_visible_monitor(window, state);
+
+    if state.fullscreen {
+        let _ = window.set_fullscreen(true);
+    } else if state.maximized {
+        let _ = window.maximize();
+    } else {
+        let _ = window.set_position(tauri::Position::Physical(tauri::PhysicalPosition {
+            x: state.x,
+            y: state.y,
+        }));
+        let _ = window.set_size(tauri::Size::Physical(tauri::PhysicalSize {
+            width: state.width,
+            height: state.height,
+        }));
+    }
+}
+
+/// Clear the saved geometry for `label`, so the next restore falls back to
+/// the window's configured defaults.
+#[tauri::command]
+pub fn reset_window_state(app: AppHandle, label: String) -> Result<(), String> {
+    let store = app.store(STORE_FILE).map_err(|e| e.to_string())?;
+    store.delete(&label);
+    store.save().map_err(|e| e.to_string())
+}
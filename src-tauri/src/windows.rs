@@ -0,0 +1,78 @@
+//! Multi-window chat sessions.
+//!
+//! The app used to assume a single `"main"` webview window. This subsystem
+//! lets the user open additional labelled windows for side-by-side
+//! conversations, and provides a cheap way to broadcast events across
+//! however many windows happen to be open.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, EventTarget, Manager, WebviewUrl, WebviewWindow, WebviewWindowBuilder};
+
+/// Monotonically increasing source for chat window labels. Must not be
+/// derived from the current window count: chat windows actually close, so
+/// that count can drop and mint a label that collides with one still open.
+static NEXT_CHAT_WINDOW_ID: AtomicUsize = AtomicUsize::new(1);
+
+/// Open a new chat window, optionally navigated straight to `chat_id`.
+/// Returns the label of the created window.
+#[tauri::command]
+pub fn open_chat_window(app: AppHandle, chat_id: Option<String>) -> Result<String, String> {
+    let label = format!("chat-{}", NEXT_CHAT_WINDOW_ID.fetch_add(1, Ordering::SeqCst));
+
+    let fragment = match &chat_id {
+        Some(id) => format!("/chat?id={}", id),
+        None => "/chat".to_string(),
+    };
+
+    let window = WebviewWindowBuilder::new(
+        &app,
+        &label,
+        WebviewUrl::App(format!("index.html#{}", fragment).into()),
+    )
+    .title("GatewayZ")
+    .inner_size(1000.0, 700.0)
+    .visible(false)
+    .build()
+    .map_err(|e| e.to_string())?;
+
+    crate::window_state::restore(&app, &window);
+    window.show().map_err(|e| e.to_string())?;
+
+    Ok(label)
+}
+
+/// List the labels of all currently open windows.
+#[tauri::command]
+pub fn list_windows(app: AppHandle) -> Result<Vec<String>, String> {
+    Ok(app.webview_windows().into_keys().collect())
+}
+
+/// Return the focused window if there is one, otherwise fall back to
+/// `"main"`, and failing that, to whatever window happens to be open.
+/// Tauri doesn't track a most-recently-focused window for us, so this is
+/// the closest approximation of "the window the user means".
+pub fn focused_or_main(app: &AppHandle) -> Option<WebviewWindow> {
+    app.webview_windows()
+        .into_values()
+        .find(|w| w.is_focused().unwrap_or(false))
+        .or_else(|| app.get_webview_window("main"))
+        .or_else(|| app.webview_windows().into_values().next())
+}
+
+/// Serialize `payload` exactly once and dispatch it to every open window
+/// whose label passes `filter`, rather than re-serializing per window as a
+/// naive loop over `windows()` would. Pass `|_| true` to broadcast to all.
+pub fn emit_to_all<S: Serialize + Clone>(
+    app: &AppHandle,
+    event: &str,
+    payload: S,
+    mut filter: impl FnMut(&str) -> bool,
+) -> Result<(), String> {
+    app.emit_filter(event, payload, |target| match target {
+        EventTarget::WebviewWindow { label } => filter(label),
+        _ => false,
+    })
+    .map_err(|e| e.to_string())
+}